@@ -1,7 +1,12 @@
 use clap::{app_from_crate, crate_authors, crate_description, crate_name, crate_version};
-use clap::{AppSettings, Arg, ArgMatches, SubCommand};
+use clap::{App, AppSettings, Arg, ArgMatches, Shell, SubCommand};
 use colored::Colorize;
 use skim::prelude::*;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Style, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::as_24_bit_terminal_escaped;
+
 use std::cmp::Ordering;
 use std::env;
 use std::fmt::Display;
@@ -14,9 +19,14 @@ use std::process::Command;
 
 // include_str!("../Cargo.toml");
 
-fn main() -> Result<(), Error> {
-    // setup subcommands and args
-    let matches = app_from_crate!()
+fn padded(text: &str) -> &'static str {
+    Box::leak(format!("{:<30}", text).into_boxed_str())
+}
+
+const LOCAL_PATH: &str = ".todo.md";
+
+fn build_app() -> App<'static, 'static> {
+    app_from_crate!()
         .settings(&[
             AppSettings::ArgsNegateSubcommands,
             AppSettings::ColoredHelp,
@@ -31,54 +41,135 @@ fn main() -> Result<(), Error> {
                 .hide_env_values(true),
         )
         .arg(Arg::with_name("editor_env").env("EDITOR").hidden(true))
+        .arg(
+            Arg::with_name("preview")
+                .long("preview")
+                .takes_value(true)
+                .possible_values(&["bat", "internal", "none"])
+                .default_value("internal")
+                .help("How to render the interactive preview pane"),
+        )
+        .arg(
+            Arg::with_name("pipe")
+                .long("pipe")
+                .takes_value(true)
+                .value_name("path")
+                .help("Read newline-delimited commands from a file (\"-\" for stdin) instead of launching skim"),
+        )
         .subcommand(
             SubCommand::with_name("view")
                 .visible_alias("v")
-                .about(format!("{:<30}", "Show existing tasks").as_str()),
+                .about(padded("Show existing tasks"))
+                .arg(Arg::with_name("highlight").long("highlight").hidden(true)),
         )
         .subcommand(
             SubCommand::with_name("add")
                 .visible_alias("a")
-                .about(format!("{:<30}", "Add new tasks").as_str()),
+                .about(padded("Add new tasks")),
         )
         .subcommand(
             SubCommand::with_name("remove")
                 .visible_alias("r")
-                .about(format!("{:<30}", "Select tasks to remove").as_str()),
+                .about(padded("Select tasks to remove")),
         )
         .subcommand(
             SubCommand::with_name("set")
                 .visible_alias("s")
-                .about(format!("{:<30}", "Change status of tasks").as_str()),
+                .about(padded("Change status of tasks")),
         )
         .subcommand(
             SubCommand::with_name("modify")
                 .visible_alias("m")
-                .about(format!("{:<30}", "Change text of tasks").as_str()),
+                .about(padded("Change text of tasks")),
         )
         .subcommand(
             SubCommand::with_name("editor")
                 .visible_alias("e")
-                .about(format!("{:<30}", "Open tasks with EDITOR").as_str()),
+                .about(padded("Open tasks with EDITOR")),
         )
         .subcommand(SubCommand::with_name("sort").about("Sort tasks by status"))
         // .subcommand(SubCommand::with_name("clean").about("Delete completed tasks"))
-        .get_matches();
+        .subcommand(
+            SubCommand::with_name("filter")
+                .visible_alias("f")
+                .about(padded("Show tasks with a chosen tag")),
+        )
+        .subcommand(
+            SubCommand::with_name("init")
+                .about("Create a new todo file in the current directory")
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .help("Overwrite an existing todo file"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("completions")
+                .about("Generate shell completion scripts")
+                .arg(
+                    Arg::with_name("shell")
+                        .possible_values(&["bash", "zsh", "fish", "powershell", "elvish"])
+                        .required(true),
+                ),
+        )
+}
+
+fn main() -> Result<(), Error> {
+    // setup subcommands and args
+    let matches = build_app().get_matches();
 
     // execute options
 
+    if let Some(completions_matches) = matches.subcommand_matches("completions") {
+        let shell = completions_matches
+            .value_of("shell")
+            .unwrap()
+            .parse::<Shell>()
+            .unwrap();
+        build_app().gen_completions_to(crate_name!(), shell, &mut std::io::stdout());
+        return Ok(());
+    }
+
+    if let Some(init_matches) = matches.subcommand_matches("init") {
+        return user_init(init_matches.is_present("force"));
+    }
+
     // set path
-    let path = get_path(&matches)?;
+    let path = match get_path(&matches) {
+        Err(e)
+            if e.kind() == ErrorKind::NotFound
+                && matches.subcommand_name().is_none()
+                && matches.value_of("pipe").is_none() =>
+        {
+            print!("no {} found here, create one? [y/N] ", LOCAL_PATH);
+            std::io::stdout().flush()?;
+
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+
+            if answer.trim().eq_ignore_ascii_case("y") {
+                user_init(false)?;
+                get_path(&matches)?
+            } else {
+                return Err(e);
+            }
+        }
+        result => result?,
+    };
 
     // read file
     let lines = read_file(&path)?;
     let mut tasks = Tasks::new(lines);
 
+    if let Some(pipe_path) = matches.value_of("pipe") {
+        return run_pipe(&mut tasks, &path, pipe_path);
+    }
+
     // execute subcommands
     let user_commands = [
         UserCommand {
             name: "view".to_string(),
-            func: Box::new(|t, _, _| user_view(t)),
+            func: Box::new(|t, p, m| user_view(t, p, m)),
         },
         UserCommand {
             name: "add".to_string(),
@@ -104,6 +195,10 @@ fn main() -> Result<(), Error> {
             name: "sort".to_string(),
             func: Box::new(|t, p, _| user_sort(t, p)),
         },
+        UserCommand {
+            name: "filter".to_string(),
+            func: Box::new(|t, p, _| user_filter(t, p)),
+        },
     ];
 
     for c in &user_commands {
@@ -119,19 +214,54 @@ fn main() -> Result<(), Error> {
     //     return Ok(());
     // }
 
-    // TODO implement interactive
-    // TODO implement init if none exists
     while tasks.interactive(&path, &matches, &user_commands)? {}
 
     Ok(())
 }
 
-fn user_view(tasks: &mut Tasks) -> Result<(), Error> {
+fn user_view(tasks: &mut Tasks, path: &Path, matches: &ArgMatches) -> Result<(), Error> {
     tasks.sort();
+
+    let highlight = matches
+        .subcommand_matches("view")
+        .map_or(false, |m| m.is_present("highlight"));
+
+    if highlight {
+        if let Some(rendered) = highlight_markdown(path) {
+            println!("\n{}", rendered);
+            return Ok(());
+        }
+    }
+
     println!("\n{}", tasks);
     Ok(())
 }
 
+// Renders the todo file with syntect's markdown syntax highlighting.
+// Returns None if no markdown syntax/theme is bundled, so callers can
+// fall back to the plain `Display` output.
+fn highlight_markdown(path: &Path) -> Option<String> {
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let syntax = syntax_set.find_syntax_by_extension("md")?;
+    let theme = ThemeSet::load_defaults()
+        .themes
+        .get("base16-ocean.dark")?
+        .clone();
+
+    let contents = std::fs::read_to_string(path).ok()?;
+    let mut highlighter = HighlightLines::new(syntax, &theme);
+    let mut rendered = String::new();
+
+    for line in contents.lines() {
+        let ranges: Vec<(Style, &str)> =
+            highlighter.highlight_line(line, &syntax_set).ok()?;
+        rendered.push_str(&as_24_bit_terminal_escaped(&ranges[..], false));
+        rendered.push('\n');
+    }
+
+    Some(rendered)
+}
+
 // consider setting status
 fn user_add(tasks: &mut Tasks, path: &Path) -> Result<(), Error> {
     let mut is_modified = false;
@@ -182,6 +312,176 @@ fn user_sort(tasks: &mut Tasks, path: &Path) -> Result<(), Error> {
     Ok(())
 }
 
+fn user_filter(tasks: &mut Tasks, _path: &Path) -> Result<(), Error> {
+    let mut tags: Vec<String> = tasks.iter().flat_map(|t| t.tags.clone()).collect();
+    tags.sort();
+    tags.dedup();
+
+    if tags.is_empty() {
+        println!("no tags found");
+        return Ok(());
+    }
+
+    let reader_option = SkimItemReaderOption::default().ansi(true).build();
+    let skim_reader = SkimItemReader::new(reader_option).of_bufread(Cursor::new(tags.join("\n")));
+    let skim_config = SkimOptionsBuilder::default()
+        .height(Some("50%"))
+        .reverse(true)
+        .prompt(Some("filter > "))
+        .build()
+        .unwrap();
+
+    let skim_output = Skim::run_with(&skim_config, Some(skim_reader));
+
+    if let Some(out) = skim_output {
+        if !out.is_abort {
+            if let Some(item) = out.selected_items.get(0) {
+                let tag = item.output().to_string();
+
+                tasks.sort();
+                println!();
+                for task in tasks.iter().filter(|t| t.tags.contains(&tag)) {
+                    println!("{}", task);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Drives `Tasks` from a script instead of skim, for editor plugins and
+// other non-interactive callers. Malformed lines don't abort the run;
+// they're collected and reported together once the whole pipe is read.
+fn run_pipe(tasks: &mut Tasks, path: &Path, pipe_path: &str) -> Result<(), Error> {
+    let mut input = String::new();
+
+    if pipe_path == "-" {
+        std::io::stdin().read_to_string(&mut input)?;
+    } else {
+        File::open(pipe_path)?.read_to_string(&mut input)?;
+    }
+
+    let mut errors: Vec<String> = Vec::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Err(e) = apply_pipe_command(tasks, line) {
+            errors.push(e);
+        }
+    }
+
+    // Persist whatever applied cleanly even if some lines failed, so a typo
+    // further down the script doesn't discard every command that ran before it.
+    write_file(path, tasks.to_file())?;
+
+    if !errors.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, errors.join("\n")));
+    }
+
+    Ok(())
+}
+
+fn apply_pipe_command(tasks: &mut Tasks, line: &str) -> Result<(), String> {
+    let mut words = line.splitn(2, char::is_whitespace);
+    let command = words.next().unwrap_or("");
+    let rest = words.next().unwrap_or("").trim();
+
+    match command {
+        "add" if !rest.is_empty() => {
+            let tags = Task::parse_tags(rest);
+            let new_id = tasks.len() + 2;
+            tasks.add(Task {
+                id: new_id,
+                text: rest.to_string(),
+                status: Status::Todo,
+                tags,
+                depth: 0,
+            });
+            Ok(())
+        }
+        "set" => {
+            let mut args = rest.splitn(2, char::is_whitespace);
+            let id = args.next().and_then(|s| s.parse::<usize>().ok());
+            let status = args.next().map(str::trim);
+
+            let index = match id.and_then(|id| tasks.index_of(id)) {
+                Some(i) => i,
+                None => return Err(format!("unknown task id in: {}", line)),
+            };
+
+            match status {
+                Some("done") => {
+                    tasks.0[index].status = Status::Done;
+                    tasks.cascade_done(index);
+                }
+                Some("todo") => tasks.0[index].status = Status::Todo,
+                Some("other") => tasks.0[index].status = Status::Other,
+                _ => return Err(format!("malformed line: {}", line)),
+            }
+
+            Ok(())
+        }
+        "remove" => match rest.parse::<usize>().ok().and_then(|id| tasks.delete_id(id)) {
+            Some(_) => Ok(()),
+            None => Err(format!("unknown task id in: {}", line)),
+        },
+        "modify" => {
+            let mut args = rest.splitn(2, char::is_whitespace);
+            let id = args.next().and_then(|s| s.parse::<usize>().ok());
+            let text = args.next().map(str::trim).filter(|t| !t.is_empty());
+
+            let task = match id.and_then(|id| tasks.get_id(id)) {
+                Some(t) => t,
+                None => return Err(format!("unknown task id in: {}", line)),
+            };
+
+            match text {
+                Some(text) => {
+                    task.tags = Task::parse_tags(text);
+                    task.text = text.to_string();
+                    Ok(())
+                }
+                None => Err(format!("malformed line: {}", line)),
+            }
+        }
+        "sort" => {
+            tasks.sort();
+            Ok(())
+        }
+        _ => Err(format!("malformed line: {}", line)),
+    }
+}
+
+fn user_init(force: bool) -> Result<(), Error> {
+    let path = Path::new(LOCAL_PATH);
+
+    if path.is_file() && !force {
+        return Err(Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists, use --force to overwrite", LOCAL_PATH),
+        ));
+    }
+
+    const TEMPLATE: &str = "- [ ] welcome to tdo, this is your first task\n";
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    let mut buf = BufWriter::new(file);
+    buf.write_all(TEMPLATE.as_bytes())?;
+    buf.flush()?;
+
+    println!("created {}", LOCAL_PATH);
+    Ok(())
+}
+
 fn read_file(path: &Path) -> Result<Vec<String>, Error> {
     let mut file = File::open(path)?;
     let mut buf = String::new();
@@ -198,7 +498,6 @@ fn write_file(path: &Path, text: String) -> Result<(), Error> {
 }
 
 fn get_path(matches: &ArgMatches) -> Result<PathBuf, Error> {
-    const LOCAL_PATH: &str = ".todo.md";
     let mut full_path = env::current_dir()?;
 
     if Path::new(LOCAL_PATH).is_file() {
@@ -234,6 +533,8 @@ struct Task {
     id: usize,
     text: String,
     status: Status,
+    tags: Vec<String>,
+    depth: usize,
 }
 
 #[derive(Eq)]
@@ -248,6 +549,23 @@ struct UserCommand {
     func: Box<dyn Fn(&mut Tasks, &Path, &ArgMatches) -> Result<(), Error>>,
 }
 
+// A skim item carrying its own preview text, so the interactive command
+// picker can show an in-process preview instead of shelling out.
+struct CommandItem {
+    name: String,
+    preview: String,
+}
+
+impl SkimItem for CommandItem {
+    fn text(&self) -> Cow<str> {
+        Cow::Borrowed(&self.name)
+    }
+
+    fn preview(&self, _context: PreviewContext) -> ItemPreview {
+        ItemPreview::AnsiText(self.preview.clone())
+    }
+}
+
 impl Tasks {
     fn new(lines: Vec<String>) -> Self {
         lines
@@ -258,7 +576,39 @@ impl Tasks {
     }
 
     fn sort(&mut self) {
-        self.0.sort();
+        let tasks = std::mem::take(&mut self.0);
+        self.0 = Tasks::sort_siblings(tasks);
+    }
+
+    // Sorts a document-order slice of tasks by recursively sorting each
+    // group of (parent, descendants) as a unit, so a parent's children
+    // stay attached to it and are only ever reordered among themselves.
+    fn sort_siblings(tasks: Vec<Task>) -> Vec<Task> {
+        let base_depth = match tasks.first() {
+            Some(t) => t.depth,
+            None => return tasks,
+        };
+
+        let mut groups: Vec<Vec<Task>> = Vec::new();
+        for task in tasks {
+            if task.depth <= base_depth {
+                groups.push(vec![task]);
+            } else if let Some(group) = groups.last_mut() {
+                group.push(task);
+            }
+        }
+
+        groups.sort_by(|a, b| a[0].cmp(&b[0]));
+
+        groups
+            .into_iter()
+            .flat_map(|mut group| {
+                let parent = group.remove(0);
+                let mut sorted = vec![parent];
+                sorted.extend(Tasks::sort_siblings(group));
+                sorted
+            })
+            .collect()
     }
 
     fn len(&mut self) -> usize {
@@ -290,11 +640,21 @@ impl Tasks {
         None
     }
 
+    // Removing a task promotes its descendants up one depth level, so they
+    // take its place among its siblings instead of being regrouped under
+    // whichever task precedes them (see sort_siblings).
     fn delete_id(&mut self, id: usize) -> Option<Task> {
-        if let Some(index) = self.index_of(id) {
-            return Some(self.remove(index));
+        let index = self.index_of(id)?;
+        let depth = self.0[index].depth;
+
+        for task in self.0.iter_mut().skip(index + 1) {
+            if task.depth <= depth {
+                break;
+            }
+            task.depth -= 1;
         }
-        None
+
+        Some(self.remove(index))
     }
 
     fn get_id(&mut self, id: usize) -> Option<&mut Task> {
@@ -329,8 +689,8 @@ impl Tasks {
     }
 
     fn set_status(&mut self, id: usize) {
-        let task = match self.get_id(id) {
-            Some(t) => t,
+        let index = match self.index_of(id) {
+            Some(i) => i,
             None => return,
         };
 
@@ -354,19 +714,34 @@ impl Tasks {
                 if let Some(item) = out.selected_items.get(0) {
                     let i = item.output().to_string();
                     if i.contains("done") {
-                        task.status = Status::Done;
+                        self.0[index].status = Status::Done;
+                        self.cascade_done(index);
                     }
                     if i.contains("todo") {
-                        task.status = Status::Todo;
+                        self.0[index].status = Status::Todo;
                     }
                     if i.contains("other") {
-                        task.status = Status::Other;
+                        self.0[index].status = Status::Other;
                     }
                 }
             }
         }
     }
 
+    // Marking a task Done also marks its subtasks Done, mirroring how
+    // checking off a parent item in a nested markdown list implies its
+    // children are finished too.
+    fn cascade_done(&mut self, index: usize) {
+        let depth = self.0[index].depth;
+
+        for task in self.0.iter_mut().skip(index + 1) {
+            if task.depth <= depth {
+                break;
+            }
+            task.status = Status::Done;
+        }
+    }
+
     fn set_text(&mut self, id: usize) {
         let task = match self.get_id(id) {
             Some(t) => t,
@@ -390,6 +765,7 @@ impl Tasks {
             let new_text = out.query;
 
             if !out.is_abort && !new_text.is_empty() {
+                task.tags = Task::parse_tags(&new_text);
                 task.text = new_text;
             }
         }
@@ -434,29 +810,78 @@ impl Tasks {
             .join("\n")
     }
 
+    // Renders the same text `tdo view` would print, in place, so a preview
+    // pane can reuse it without re-exec'ing the binary.
+    fn preview_text(&mut self) -> String {
+        self.sort();
+        format!("{}", self)
+    }
+
     fn interactive(
         &mut self,
         path: &Path,
         matches: &ArgMatches,
         user_commands: &[UserCommand],
     ) -> Result<bool, Error> {
-        let preview_path = format!("{:?} view", env::current_exe()?);
         let command_names = user_commands
             .iter()
             .map(|c| c.name.as_str())
             .collect::<Vec<_>>()
             .join("\n");
-        let reader_option = SkimItemReaderOption::default().ansi(true).build();
-        let skim_reader = SkimItemReader::new(reader_option).of_bufread(Cursor::new(command_names));
-        let skim_config = SkimOptionsBuilder::default()
-            .height(Some("50%"))
-            .reverse(true)
-            .preview(Some(preview_path.as_str()))
-            .preview_window(Some("right:80%"))
-            .build()
-            .unwrap();
 
-        let skim_output = Skim::run_with(&skim_config, Some(skim_reader));
+        let skim_output = match matches.value_of("preview").unwrap_or("internal") {
+            "none" => {
+                let reader_option = SkimItemReaderOption::default().ansi(true).build();
+                let skim_reader =
+                    SkimItemReader::new(reader_option).of_bufread(Cursor::new(command_names));
+                let skim_config = SkimOptionsBuilder::default()
+                    .height(Some("50%"))
+                    .reverse(true)
+                    .build()
+                    .unwrap();
+
+                Skim::run_with(&skim_config, Some(skim_reader))
+            }
+            "bat" => {
+                let preview_cmd = format!("bat --color=always --style=plain {:?}", path);
+                let reader_option = SkimItemReaderOption::default().ansi(true).build();
+                let skim_reader =
+                    SkimItemReader::new(reader_option).of_bufread(Cursor::new(command_names));
+                let skim_config = SkimOptionsBuilder::default()
+                    .height(Some("50%"))
+                    .reverse(true)
+                    .preview(Some(preview_cmd.as_str()))
+                    .preview_window(Some("right:80%"))
+                    .build()
+                    .unwrap();
+
+                Skim::run_with(&skim_config, Some(skim_reader))
+            }
+            _ => {
+                // Renders the preview in-process via `highlight_markdown`
+                // instead of shelling out to `tdo view` on every redraw.
+                let preview = highlight_markdown(path).unwrap_or_else(|| self.preview_text());
+
+                let (tx, rx): (SkimItemSender, SkimItemReceiver) = unbounded();
+                for name in command_names.lines() {
+                    let _ = tx.send(Arc::new(CommandItem {
+                        name: name.to_string(),
+                        preview: preview.clone(),
+                    }) as Arc<dyn SkimItem>);
+                }
+                drop(tx);
+
+                let skim_config = SkimOptionsBuilder::default()
+                    .height(Some("50%"))
+                    .reverse(true)
+                    .preview(Some(""))
+                    .preview_window(Some("right:80%"))
+                    .build()
+                    .unwrap();
+
+                Skim::run_with(&skim_config, Some(rx))
+            }
+        };
 
         if let Some(out) = skim_output {
             if !out.is_abort {
@@ -498,6 +923,8 @@ impl FromIterator<Task> for Tasks {
 
 impl Task {
     fn parse(id: usize, line: &str) -> Self {
+        let depth = Task::parse_depth(line);
+        let line = line.trim_start();
         let text;
 
         if line.starts_with('-') {
@@ -507,26 +934,60 @@ impl Task {
         }
 
         if text.starts_with("[ ]") {
+            let text = text.replacen("[ ]", "", 1).trim().to_string();
+            let tags = Task::parse_tags(&text);
             return Task {
                 id,
-                text: text.replacen("[ ]", "", 1).trim().to_string(),
+                text,
                 status: Status::Todo,
+                tags,
+                depth,
             };
         }
 
         if text.starts_with("[x]") {
+            let text = text.replacen("[x]", "", 1).trim().to_string();
+            let tags = Task::parse_tags(&text);
             return Task {
                 id,
-                text: text.replacen("[x]", "", 1).trim().to_string(),
+                text,
                 status: Status::Done,
+                tags,
+                depth,
             };
         }
 
+        let tags = Task::parse_tags(&text);
         Task {
             id,
             text,
             status: Status::Other,
+            tags,
+            depth,
+        }
+    }
+
+    // Every 2 leading spaces, or every leading tab, counts as one level
+    // of nesting under the previous bullet.
+    fn parse_depth(line: &str) -> usize {
+        let mut depth = 0;
+        let mut spaces = 0;
+
+        for c in line.chars() {
+            match c {
+                '\t' => depth += 1,
+                ' ' => {
+                    spaces += 1;
+                    if spaces == 2 {
+                        depth += 1;
+                        spaces = 0;
+                    }
+                }
+                _ => break,
+            }
         }
+
+        depth
     }
 
     fn parse_id(line: &str) -> Option<usize> {
@@ -536,23 +997,53 @@ impl Task {
         None
     }
 
+    fn parse_tags(text: &str) -> Vec<String> {
+        fn is_tag_char(c: char) -> bool {
+            c.is_ascii_alphanumeric() || c == '_' || c == '-'
+        }
+
+        let chars: Vec<char> = text.chars().collect();
+        let mut tags = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i] == '#' && (i == 0 || !is_tag_char(chars[i - 1])) {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && is_tag_char(chars[end]) {
+                    end += 1;
+                }
+                if end > start {
+                    tags.push(chars[start..end].iter().collect());
+                    i = end;
+                    continue;
+                }
+            }
+            i += 1;
+        }
+
+        tags
+    }
+
     fn to_file(&self) -> String {
+        let indent = "  ".repeat(self.depth);
+
         match self {
             Task {
                 status: Status::Todo,
                 text,
                 ..
-            } => format!("- [ ] {}", text),
+            } => format!("{}- [ ] {}", indent, text),
             Task {
                 status: Status::Done,
                 text,
                 ..
-            } => format!("- [x] {}", text),
+            } => format!("{}- [x] {}", indent, text),
             Task {
                 status: Status::Other,
                 text,
                 ..
-            } => format!("- {}", text),
+            } => format!("{}- {}", indent, text),
         }
     }
 
@@ -573,10 +1064,13 @@ impl Task {
             let new_text = out.query;
 
             if !out.is_abort && !new_text.is_empty() {
+                let tags = Task::parse_tags(&new_text);
                 return Some(Task {
                     id: len + 2,
                     text: new_text,
                     status: Status::Todo,
+                    tags,
+                    depth: 0,
                 });
             }
         }
@@ -588,22 +1082,26 @@ impl Task {
 impl Display for Task {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
         colored::control::set_override(true);
+        let indent = "  ".repeat(self.depth);
         match self {
             Task {
                 id,
                 text,
                 status: Status::Todo,
-            } => f.write_fmt(format_args!("{:>5} | {} {}", id, "✕".red(), text)),
+                ..
+            } => f.write_fmt(format_args!("{:>5} | {}{} {}", id, indent, "✕".red(), text)),
             Task {
                 id,
                 text,
                 status: Status::Done,
-            } => f.write_fmt(format_args!("{:>5} | {} {}", id, "✓".green(), text)),
+                ..
+            } => f.write_fmt(format_args!("{:>5} | {}{} {}", id, indent, "✓".green(), text)),
             Task {
                 id,
                 text,
                 status: Status::Other,
-            } => f.write_fmt(format_args!("{:>5} | {}", id, text)),
+                ..
+            } => f.write_fmt(format_args!("{:>5} | {}{}", id, indent, text)),
         }
     }
 }
@@ -632,7 +1130,10 @@ impl PartialOrd for Task {
 
 impl PartialEq for Task {
     fn eq(&self, other: &Self) -> bool {
-        self.status == other.status && self.id == other.id && self.text == other.text
+        self.status == other.status
+            && self.id == other.id
+            && self.text == other.text
+            && self.depth == other.depth
     }
 }
 